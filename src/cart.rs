@@ -1,19 +1,115 @@
 use crate::catalog::Catalog;
 use crate::ensure; // Import the ensure macro
+use crate::order::{Order, OrderLineItem};
+use crate::promotion::Promotion;
+use crate::quantity_unit::QuantityUnit;
 use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 use uuid::Uuid;
 
 const MIN_ITEM_COUNT: u32 = 1;
 const MAX_ITEM_COUNT: u32 = 100;
 
+/// A line item in a shopping cart: a quantity of a product variant, in a given unit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineItem {
+    pub quantity: u32,
+    pub unit: QuantityUnit,
+}
+
 /// Represents a shopping cart with a unique ID, customer ID, items, and a catalog.
 #[derive(Debug)]
 pub struct ShoppingCart {
     id: Uuid,
     customer_id: String,
-    items: HashMap<String, u32>,
+    items: HashMap<(String, Option<String>), LineItem>,
+    catalog: Catalog,
+    promotions: Vec<Promotion>,
+}
+
+/// A cart line item flattened to a record, since JSON object keys must be strings and can't
+/// represent the `(item_name, variant)` tuple `ShoppingCart::items` is keyed by.
+#[derive(Debug, Serialize, Deserialize)]
+struct LineItemRecord {
+    name: String,
+    variant: Option<String>,
+    quantity: u32,
+    unit: QuantityUnit,
+}
+
+/// The on-the-wire representation of a `ShoppingCart`, serialized and deserialized manually
+/// because its `items` map has a non-string key.
+#[derive(Serialize)]
+struct ShoppingCartRecordRef<'a> {
+    id: Uuid,
+    customer_id: &'a str,
+    items: Vec<LineItemRecord>,
+    catalog: &'a Catalog,
+    promotions: &'a [Promotion],
+}
+
+#[derive(Deserialize)]
+struct ShoppingCartRecordOwned {
+    id: Uuid,
+    customer_id: String,
+    items: Vec<LineItemRecord>,
     catalog: Catalog,
+    promotions: Vec<Promotion>,
+}
+
+impl Serialize for ShoppingCart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let record = ShoppingCartRecordRef {
+            id: self.id,
+            customer_id: &self.customer_id,
+            items: self
+                .items
+                .iter()
+                .map(|((name, variant), line)| LineItemRecord {
+                    name: name.clone(),
+                    variant: variant.clone(),
+                    quantity: line.quantity,
+                    unit: line.unit,
+                })
+                .collect(),
+            catalog: &self.catalog,
+            promotions: &self.promotions,
+        };
+        record.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ShoppingCart {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let record = ShoppingCartRecordOwned::deserialize(deserializer)?;
+        let items = record
+            .items
+            .into_iter()
+            .map(|item| {
+                (
+                    (item.name, item.variant),
+                    LineItem {
+                        quantity: item.quantity,
+                        unit: item.unit,
+                    },
+                )
+            })
+            .collect();
+        Ok(ShoppingCart {
+            id: record.id,
+            customer_id: record.customer_id,
+            items,
+            catalog: record.catalog,
+            promotions: record.promotions,
+        })
+    }
 }
 
 impl ShoppingCart {
@@ -36,6 +132,7 @@ impl ShoppingCart {
             customer_id: customer_id.to_string(),
             items: HashMap::default(),
             catalog: Catalog::new(),
+            promotions: Vec::new(),
         })
     }
 
@@ -63,25 +160,39 @@ impl ShoppingCart {
         &self.customer_id
     }
 
-    /// Returns a reference to the items in the shopping cart.
-    pub fn items(&self) -> &HashMap<String, u32> {
+    /// Returns a reference to the items in the shopping cart, keyed by item name and variant.
+    pub fn items(&self) -> &HashMap<(String, Option<String>), LineItem> {
         &self.items
     }
 
-    /// Adds an item to the shopping cart.
+    /// Returns a mutable reference to the cart's catalog, so callers can configure which
+    /// variants and units an item is sellable as before adding it to the cart.
+    pub fn catalog_mut(&mut self) -> &mut Catalog {
+        &mut self.catalog
+    }
+
+    /// Adds an item variant to the shopping cart.
     ///
     /// # Arguments
     ///
     /// * `name` - The name of the item to add.
+    /// * `variant` - The variant descriptor of the item (e.g. `"Red / Large"`), or `None`.
     /// * `quantity` - The quantity of the item to add.
+    /// * `unit` - The unit the quantity is measured in.
     ///
     /// # Errors
     ///
-    /// Returns an error if the quantity is zero, the item is not found in the catalog,
-    /// or the quantity exceeds the maximum limit.
-    pub fn add_item(&mut self, name: &str, quantity: u32) -> Result<(), String> {
+    /// Returns an error if the item/variant/unit combination is not sellable in the catalog,
+    /// or the quantity is out of range or exceeds the maximum limit.
+    pub fn add_item(
+        &mut self,
+        name: &str,
+        variant: Option<&str>,
+        quantity: u32,
+        unit: QuantityUnit,
+    ) -> Result<(), String> {
         ensure!(
-            self.catalog.has_item(name),
+            self.catalog.is_sellable(name, variant, unit),
             format!("Item not found in the catalog: '{}'", name)
         );
         ensure!(
@@ -91,32 +202,47 @@ impl ShoppingCart {
                 name, MIN_ITEM_COUNT, MAX_ITEM_COUNT
             )
         );
-        let counter = self.items.entry(name.to_string()).or_insert(0);
+        let key = (name.to_string(), variant.map(|v| v.to_string()));
+        let line = self.items.entry(key).or_insert(LineItem { quantity: 0, unit });
         ensure!(
-            *counter + quantity <= MAX_ITEM_COUNT,
+            line.unit == unit,
+            format!(
+                "Item '{}' is already in the cart measured in {:?}, cannot add it in {:?}",
+                name, line.unit, unit
+            )
+        );
+        ensure!(
+            line.quantity + quantity <= MAX_ITEM_COUNT,
             format!(
                 "Adding {} of '{}' exceeds the limit of {}",
                 quantity, name, MAX_ITEM_COUNT
             )
         );
-        *counter += quantity;
+        line.quantity += quantity;
         Ok(())
     }
 
-    /// Updates the quantity of an item in the shopping cart.
+    /// Updates the quantity of an item variant in the shopping cart.
     ///
     /// # Arguments
     ///
     /// * `name` - The name of the item to update.
+    /// * `variant` - The variant descriptor of the item, or `None`.
     /// * `quantity` - The new quantity of the item.
     ///
     /// # Errors
     ///
     /// Returns an error if the item is not found in the cart or the quantity is out of range.
-    pub fn update_item(&mut self, name: &str, quantity: u32) -> Result<(), String> {
-        match self.items.get_mut(name) {
-            Some(counter) if (MIN_ITEM_COUNT..=MAX_ITEM_COUNT).contains(&quantity) => {
-                *counter = quantity;
+    pub fn update_item(
+        &mut self,
+        name: &str,
+        variant: Option<&str>,
+        quantity: u32,
+    ) -> Result<(), String> {
+        let key = (name.to_string(), variant.map(|v| v.to_string()));
+        match self.items.get_mut(&key) {
+            Some(line) if (MIN_ITEM_COUNT..=MAX_ITEM_COUNT).contains(&quantity) => {
+                line.quantity = quantity;
                 Ok(())
             }
             Some(_) => Err(format!(
@@ -127,36 +253,204 @@ impl ShoppingCart {
         }
     }
 
-    /// Removes an item from the shopping cart.
+    /// Removes an item variant from the shopping cart.
     ///
     /// # Arguments
     ///
     /// * `name` - The name of the item to remove.
+    /// * `variant` - The variant descriptor of the item, or `None`.
     ///
     /// # Errors
     ///
     /// Returns an error if the item is not found in the cart.
-    pub fn remove_item(&mut self, name: &str) -> Result<(), String> {
-        if self.items.remove(name).is_some() {
+    pub fn remove_item(&mut self, name: &str, variant: Option<&str>) -> Result<(), String> {
+        let key = (name.to_string(), variant.map(|v| v.to_string()));
+        if self.items.remove(&key).is_some() {
             Ok(())
         } else {
             Err(format!("Item not found in the cart: '{}'", name))
         }
     }
 
-    /// Calculates the total cost of the items in the shopping cart.
+    /// Adds a promotion to the cart, to be consulted by `get_total_cost`.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// The total cost of the items in the shopping cart.
-    pub fn get_total_cost(&self) -> f64 {
+    /// * `promo` - The promotion rule to apply.
+    pub fn apply_promotion(&mut self, promo: Promotion) {
+        self.promotions.push(promo);
+    }
+
+    /// Calculates the undiscounted subtotal of the items in the shopping cart.
+    fn subtotal(&self) -> f64 {
         self.items
             .iter()
-            .filter_map(|(name, &quantity)| {
+            .filter_map(|((name, _), line)| {
                 self.catalog
                     .get_price(name)
-                    .map(|price| price * quantity as f64)
+                    .map(|price| price * line.quantity as f64)
             })
             .sum()
     }
+
+    /// Calculates the undiscounted subtotal of a single item across all of its variant lines.
+    fn item_subtotal(&self, item_name: &str) -> f64 {
+        let Some(price) = self.catalog.get_price(item_name) else {
+            return 0.0;
+        };
+        let quantity: u32 = self
+            .items
+            .iter()
+            .filter(|((name, _), _)| name == item_name)
+            .map(|(_, line)| line.quantity)
+            .sum();
+        price * quantity as f64
+    }
+
+    /// Calculates how much a `BuyNGetMFree` promotion saves: for every `buy + free` units of
+    /// `item_name` across its variant lines, `free` of them are free.
+    fn buy_n_get_m_free_discount(&self, item_name: &str, buy: u32, free: u32) -> f64 {
+        let group_size = buy.saturating_add(free);
+        if group_size == 0 {
+            return 0.0;
+        }
+        let Some(price) = self.catalog.get_price(item_name) else {
+            return 0.0;
+        };
+        let quantity: u32 = self
+            .items
+            .iter()
+            .filter(|((name, _), _)| name == item_name)
+            .map(|(_, line)| line.quantity)
+            .sum();
+        let free_units = (quantity / group_size) * free;
+        free_units as f64 * price
+    }
+
+    /// Calculates how much a single promotion saves off the given running total.
+    fn discount_for(&self, promo: &Promotion, running_total: f64) -> f64 {
+        match promo {
+            Promotion::PercentOff { item, percent } => {
+                let base = match item {
+                    Some(item_name) => self.item_subtotal(item_name),
+                    None => running_total,
+                };
+                base * (percent / 100.0)
+            }
+            Promotion::BuyNGetMFree { item, buy, free } => {
+                self.buy_n_get_m_free_discount(item, *buy, *free)
+            }
+            Promotion::SpendThreshold {
+                min_total,
+                flat_off,
+            } => {
+                if running_total >= *min_total {
+                    *flat_off
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// Calculates the total cost of the items in the shopping cart, after folding the
+    /// cart's promotions over the undiscounted subtotal. Never goes below `0.0`.
+    ///
+    /// # Returns
+    ///
+    /// The discounted total cost of the items in the shopping cart.
+    pub fn get_total_cost(&self) -> f64 {
+        let total = self.promotions.iter().fold(self.subtotal(), |running_total, promo| {
+            running_total - self.discount_for(promo, running_total)
+        });
+        total.max(0.0)
+    }
+
+    /// Breaks down how much each applied promotion saved, in the order they were applied.
+    ///
+    /// # Returns
+    ///
+    /// A vector pairing each promotion with the amount it saved off the running total.
+    pub fn get_discount_breakdown(&self) -> Vec<(Promotion, f64)> {
+        let mut running_total = self.subtotal();
+        self.promotions
+            .iter()
+            .map(|promo| {
+                let saved = self.discount_for(promo, running_total);
+                running_total -= saved;
+                (promo.clone(), saved)
+            })
+            .collect()
+    }
+
+    /// Groups the subtotal cost of the items in the cart by their catalog category.
+    ///
+    /// # Returns
+    ///
+    /// A map from category name to the summed subtotal of cart items in that category.
+    pub fn get_cost_by_category(&self) -> HashMap<String, f64> {
+        let mut totals: HashMap<String, f64> = HashMap::new();
+        for ((name, _), line) in &self.items {
+            if let (Some(price), Some(category)) =
+                (self.catalog.get_price(name), self.catalog.get_category(name))
+            {
+                *totals.entry(category.to_string()).or_insert(0.0) += price * line.quantity as f64;
+            }
+        }
+        totals
+    }
+
+    /// Freezes the cart into an immutable `Order`, resolving each line item's price
+    /// from the catalog so later catalog price changes can't affect the order.
+    ///
+    /// # Arguments
+    ///
+    /// * `note` - An optional free-text note from the buyer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cart has no items.
+    pub fn checkout(self, note: Option<String>) -> Result<Order, String> {
+        ensure!(!self.items.is_empty(), "Cannot check out an empty cart".to_string());
+
+        let mut line_items = Vec::with_capacity(self.items.len());
+        for ((name, variant), line) in &self.items {
+            let unit_price = self
+                .catalog
+                .get_price(name)
+                .ok_or_else(|| format!("Item not found in the catalog: '{}'", name))?;
+            line_items.push(OrderLineItem {
+                name: name.clone(),
+                variant: variant.clone(),
+                quantity: line.quantity,
+                unit: line.unit,
+                unit_price,
+            });
+        }
+
+        // Snapshot the total the customer was actually shown, promotions included,
+        // before the cart is consumed.
+        let total = self.get_total_cost();
+
+        Ok(Order::new(self.id, self.customer_id, line_items, total, note))
+    }
+
+    /// Serializes the cart to a JSON string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| e.to_string())
+    }
+
+    /// Restores a cart, with its id, customer id, and line items intact, from a JSON string
+    /// produced by `to_json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is malformed or does not match the expected shape.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
 }
@@ -1,16 +1,34 @@
 use crate::ensure;
+use crate::quantity_unit::QuantityUnit;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 const MIN_ITEM_NAME_LENGTH: usize = 3;
 const MAX_ITEM_NAME_LENGTH: usize = 20;
 const MIN_ITEM_PRICE: f64 = 1.0;
 const MAX_ITEM_PRICE: f64 = 10000.0;
+const DEFAULT_CATEGORY: &str = "Uncategorized";
 
-/// A catalog of items with their prices.
-#[derive(Debug)]
+/// A catalog entry holding an item's price, category, and the variants/units it can be sold in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CatalogItem {
+    price: f64,
+    category: String,
+    variants: Vec<String>,
+    units: Vec<QuantityUnit>,
+}
+
+/// A catalog of items with their prices and categories.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Catalog {
-    items: HashMap<String, f64>,
+    items: HashMap<String, CatalogItem>,
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Catalog {
@@ -39,7 +57,7 @@ impl Catalog {
         catalog
     }
 
-    /// Adds an item to the catalog.
+    /// Adds an item to the catalog, defaulting its category to `"Uncategorized"`.
     ///
     /// # Arguments
     ///
@@ -51,6 +69,27 @@ impl Catalog {
     /// * `Ok(())` if the item was added successfully.
     /// * `Err(String)` if the item name or price is invalid.
     fn add_item(&mut self, item_name: &str, price: f64) -> Result<(), String> {
+        self.add_item_with_category(item_name, price, DEFAULT_CATEGORY)
+    }
+
+    /// Adds an item to the catalog under the given category.
+    ///
+    /// # Arguments
+    ///
+    /// * `item_name` - The name of the item to add.
+    /// * `price` - The price of the item.
+    /// * `category` - The category the item belongs to.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the item was added successfully.
+    /// * `Err(String)` if the item name or price is invalid.
+    pub fn add_item_with_category(
+        &mut self,
+        item_name: &str,
+        price: f64,
+        category: &str,
+    ) -> Result<(), String> {
         ensure!(
             Self::is_valid_item_name(item_name),
             format!(
@@ -65,10 +104,82 @@ impl Catalog {
                 MIN_ITEM_PRICE, MAX_ITEM_PRICE
             )
         );
-        self.items.insert(item_name.to_string(), price);
+        self.items.insert(
+            item_name.to_string(),
+            CatalogItem {
+                price,
+                category: category.to_string(),
+                variants: Vec::new(),
+                units: vec![QuantityUnit::Piece],
+            },
+        );
         Ok(())
     }
 
+    /// Sets the variant descriptors an item can be sold as (e.g. `"Red / Large"`).
+    ///
+    /// # Arguments
+    ///
+    /// * `item_name` - The name of the item.
+    /// * `variants` - The variant descriptors the item is sellable as.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the item is not found in the catalog.
+    pub fn set_variants(&mut self, item_name: &str, variants: Vec<String>) -> Result<(), String> {
+        match self.items.get_mut(item_name) {
+            Some(item) => {
+                item.variants = variants;
+                Ok(())
+            }
+            None => Err(format!("Item not found in the catalog: '{}'", item_name)),
+        }
+    }
+
+    /// Sets the units an item can be sold in.
+    ///
+    /// # Arguments
+    ///
+    /// * `item_name` - The name of the item.
+    /// * `units` - The quantity units the item is sellable in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the item is not found in the catalog.
+    pub fn set_units(&mut self, item_name: &str, units: Vec<QuantityUnit>) -> Result<(), String> {
+        match self.items.get_mut(item_name) {
+            Some(item) => {
+                item.units = units;
+                Ok(())
+            }
+            None => Err(format!("Item not found in the catalog: '{}'", item_name)),
+        }
+    }
+
+    /// Checks whether an item can be sold in the given variant/unit combination.
+    ///
+    /// # Arguments
+    ///
+    /// * `item_name` - The name of the item.
+    /// * `variant` - The variant descriptor, or `None` for the base item.
+    /// * `unit` - The quantity unit the item would be sold in.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the item exists and is sellable in that variant/unit combination.
+    pub fn is_sellable(&self, item_name: &str, variant: Option<&str>, unit: QuantityUnit) -> bool {
+        match self.items.get(item_name) {
+            Some(item) => {
+                let variant_ok = match variant {
+                    Some(v) => item.variants.iter().any(|allowed| allowed == v),
+                    None => item.variants.is_empty(),
+                };
+                variant_ok && item.units.contains(&unit)
+            }
+            None => false,
+        }
+    }
+
     /// Checks if the item price is valid.
     ///
     /// # Arguments
@@ -123,7 +234,54 @@ impl Catalog {
     ///
     /// `Some(f64)` with the price if the item is found, otherwise `None`.
     pub fn get_price(&self, item_name: &str) -> Option<f64> {
-        self.items.get(item_name).copied()
+        self.items.get(item_name).map(|item| item.price)
+    }
+
+    /// Gets the category of an item.
+    ///
+    /// # Arguments
+    ///
+    /// * `item_name` - The name of the item.
+    ///
+    /// # Returns
+    ///
+    /// `Some(&str)` with the category if the item is found, otherwise `None`.
+    pub fn get_category(&self, item_name: &str) -> Option<&str> {
+        self.items.get(item_name).map(|item| item.category.as_str())
+    }
+
+    /// Lists the names of all items in a given category.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The category to filter items by.
+    ///
+    /// # Returns
+    ///
+    /// A vector of item names that belong to the category.
+    pub fn items_in_category(&self, category: &str) -> Vec<&str> {
+        self.items
+            .iter()
+            .filter(|(_, item)| item.category == category)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Lists the distinct categories present in the catalog.
+    ///
+    /// # Returns
+    ///
+    /// A vector of the distinct category names in the catalog.
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = self
+            .items
+            .values()
+            .map(|item| item.category.clone())
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .collect();
+        categories.sort();
+        categories
     }
 }
 
@@ -180,4 +338,75 @@ mod tests {
         assert!(catalog.add_item("الهاتف الذكي", 999.99).is_ok());
         assert!(catalog.has_item("الهاتف الذكي"));
     }
+
+    #[test]
+    fn test_default_items_uncategorized() {
+        let catalog = Catalog::new();
+        assert_eq!(catalog.get_category("Laptop"), Some("Uncategorized"));
+        assert_eq!(catalog.categories(), vec!["Uncategorized".to_string()]);
+    }
+
+    #[test]
+    fn test_add_item_with_category() {
+        let mut catalog = Catalog::new();
+        assert!(catalog
+            .add_item_with_category("Smartphone", 799.99, "Electronics")
+            .is_ok());
+        assert_eq!(catalog.get_category("Smartphone"), Some("Electronics"));
+
+        let mut categories = catalog.categories();
+        categories.sort();
+        assert_eq!(
+            categories,
+            vec!["Electronics".to_string(), "Uncategorized".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_items_in_category() {
+        let mut catalog = Catalog::new();
+        catalog
+            .add_item_with_category("Smartphone", 799.99, "Electronics")
+            .unwrap();
+        catalog
+            .add_item_with_category("Tablet", 599.99, "Electronics")
+            .unwrap();
+
+        let mut electronics = catalog.items_in_category("Electronics");
+        electronics.sort();
+        assert_eq!(electronics, vec!["Smartphone", "Tablet"]);
+
+        assert_eq!(catalog.items_in_category("Nonexistent"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_default_item_sellable_as_piece_only() {
+        let catalog = Catalog::new();
+        assert!(catalog.is_sellable("Laptop", None, QuantityUnit::Piece));
+        assert!(!catalog.is_sellable("Laptop", None, QuantityUnit::Kilogram));
+        assert!(!catalog.is_sellable("Laptop", Some("Red / Large"), QuantityUnit::Piece));
+    }
+
+    #[test]
+    fn test_sellable_with_variants_and_units() {
+        let mut catalog = Catalog::new();
+        catalog
+            .set_variants("Laptop", vec!["Red / Large".to_string()])
+            .unwrap();
+        catalog
+            .set_units("Laptop", vec![QuantityUnit::Piece, QuantityUnit::Kilogram])
+            .unwrap();
+
+        assert!(catalog.is_sellable("Laptop", Some("Red / Large"), QuantityUnit::Piece));
+        assert!(catalog.is_sellable("Laptop", Some("Red / Large"), QuantityUnit::Kilogram));
+        assert!(!catalog.is_sellable("Laptop", Some("Blue / Small"), QuantityUnit::Piece));
+        assert!(!catalog.is_sellable("Laptop", None, QuantityUnit::Piece));
+    }
+
+    #[test]
+    fn test_set_variants_item_not_found() {
+        let mut catalog = Catalog::new();
+        assert!(catalog.set_variants("Tablet", vec![]).is_err());
+        assert!(catalog.set_units("Tablet", vec![]).is_err());
+    }
 }
@@ -0,0 +1,23 @@
+mod cart;
+mod catalog;
+mod order;
+mod promotion;
+mod quantity_unit;
+mod repository;
+
+pub use cart::{LineItem, ShoppingCart};
+pub use catalog::Catalog;
+pub use order::{Order, OrderLineItem};
+pub use promotion::Promotion;
+pub use quantity_unit::QuantityUnit;
+pub use repository::CartRepository;
+
+/// Returns an error from the enclosing function unless the given condition holds.
+#[macro_export]
+macro_rules! ensure {
+    ($cond:expr, $err:expr) => {
+        if !($cond) {
+            return Err($err);
+        }
+    };
+}
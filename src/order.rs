@@ -0,0 +1,83 @@
+use crate::quantity_unit::QuantityUnit;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+/// A snapshot of a single cart line item captured at checkout time, with its
+/// per-unit price resolved from the catalog so later price changes can't affect it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderLineItem {
+    pub name: String,
+    pub variant: Option<String>,
+    pub quantity: u32,
+    pub unit: QuantityUnit,
+    pub unit_price: f64,
+}
+
+/// An immutable snapshot of a shopping cart taken at checkout time.
+#[derive(Debug, Clone)]
+pub struct Order {
+    id: Uuid,
+    cart_id: Uuid,
+    customer_id: String,
+    items: Vec<OrderLineItem>,
+    total: f64,
+    note: Option<String>,
+    created_at: SystemTime,
+}
+
+impl Order {
+    /// Creates a new order snapshot. Only `ShoppingCart::checkout` constructs orders,
+    /// so that an order's line items and total are always resolved from a real cart.
+    pub(crate) fn new(
+        cart_id: Uuid,
+        customer_id: String,
+        items: Vec<OrderLineItem>,
+        total: f64,
+        note: Option<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            cart_id,
+            customer_id,
+            items,
+            total,
+            note,
+            created_at: SystemTime::now(),
+        }
+    }
+
+    /// Returns the unique ID of the order.
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Returns the ID of the cart this order was checked out from.
+    pub fn cart_id(&self) -> Uuid {
+        self.cart_id
+    }
+
+    /// Returns the customer ID associated with the order.
+    pub fn customer_id(&self) -> &str {
+        &self.customer_id
+    }
+
+    /// Returns the line items captured in the order.
+    pub fn items(&self) -> &[OrderLineItem] {
+        &self.items
+    }
+
+    /// Returns the grand total of the order.
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+
+    /// Returns the buyer's free-text note, if one was provided at checkout.
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    /// Returns the time the order was created.
+    pub fn created_at(&self) -> SystemTime {
+        self.created_at
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// A discount rule that a `ShoppingCart` can hold and apply at checkout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Promotion {
+    /// A percentage discount, cart-wide when `item` is `None`, otherwise scoped to that item.
+    PercentOff { item: Option<String>, percent: f64 },
+    /// For every `buy` units of `item` purchased, `free` more are free.
+    BuyNGetMFree { item: String, buy: u32, free: u32 },
+    /// A flat discount applied once the running total reaches `min_total`.
+    SpendThreshold { min_total: f64, flat_off: f64 },
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+/// The unit in which an item's quantity is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuantityUnit {
+    Piece,
+    Kilogram,
+    Liter,
+}
@@ -0,0 +1,63 @@
+use crate::cart::ShoppingCart;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// An in-memory repository of shopping carts, keyed by their unique ID.
+#[derive(Debug, Default)]
+pub struct CartRepository {
+    carts: HashMap<Uuid, ShoppingCart>,
+}
+
+impl CartRepository {
+    /// Creates a new, empty cart repository.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Saves a cart, overwriting any existing cart with the same ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - The cart to save.
+    pub fn save(&mut self, cart: ShoppingCart) {
+        self.carts.insert(cart.id(), cart);
+    }
+
+    /// Looks up a cart by its ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the cart to look up.
+    ///
+    /// # Returns
+    ///
+    /// `Some(&ShoppingCart)` if a cart with that ID is stored, otherwise `None`.
+    pub fn get(&self, id: &Uuid) -> Option<&ShoppingCart> {
+        self.carts.get(id)
+    }
+
+    /// Looks up all carts belonging to a given customer.
+    ///
+    /// # Arguments
+    ///
+    /// * `customer_id` - The customer ID to look up carts for.
+    ///
+    /// # Returns
+    ///
+    /// A vector of references to the carts belonging to that customer.
+    pub fn get_by_customer(&self, customer_id: &str) -> Vec<&ShoppingCart> {
+        self.carts
+            .values()
+            .filter(|cart| cart.customer_id() == customer_id)
+            .collect()
+    }
+
+    /// Removes a cart from the repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the cart to remove.
+    pub fn remove(&mut self, id: &Uuid) {
+        self.carts.remove(id);
+    }
+}
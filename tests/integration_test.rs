@@ -1,4 +1,4 @@
-use rusty_shopping_cart::ShoppingCart;
+use rusty_shopping_cart::{CartRepository, Promotion, QuantityUnit, ShoppingCart};
 
 #[test]
 fn test_new_cart() {
@@ -36,7 +36,13 @@ fn test_items_immutable() {
     // without the clone line below, Rust will not allow us to modify the items!
     let mut items = items.clone();
     // because this is a clone, modifying it will not affect the original cart
-    items.insert("Laptop".to_string(), 1);
+    items.insert(
+        ("Laptop".to_string(), None),
+        rusty_shopping_cart::LineItem {
+            quantity: 1,
+            unit: QuantityUnit::Piece,
+        },
+    );
     assert_eq!(cart.items().len(), 0);
 }
 
@@ -52,29 +58,127 @@ fn test_add_item() {
     let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
     assert_eq!(cart.items().len(), 0);
 
-    cart.add_item("Laptop", 1).unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
     assert_eq!(cart.items().len(), 1);
 
-    cart.add_item("Laptop", 1).unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
     assert_eq!(cart.items().len(), 1);
 
-    cart.add_item("Mouse", 1).unwrap();
+    cart.add_item("Mouse", None, 1, QuantityUnit::Piece).unwrap();
     assert_eq!(cart.items().len(), 2);
 
-    cart.add_item("Keyboard", 1).unwrap();
+    cart.add_item("Keyboard", None, 1, QuantityUnit::Piece).unwrap();
     assert_eq!(cart.items().len(), 3);
 
-    cart.add_item("Monitor", 1).unwrap();
+    cart.add_item("Monitor", None, 1, QuantityUnit::Piece).unwrap();
     assert_eq!(cart.items().len(), 4);
 
-    cart.add_item("Headphones", 1).unwrap();
+    cart.add_item("Headphones", None, 1, QuantityUnit::Piece).unwrap();
     assert_eq!(cart.items().len(), 5);
 
-    assert_eq!(cart.items().get("Laptop").unwrap(), &2);
-    assert_eq!(cart.items().get("Mouse").unwrap(), &1);
-    assert_eq!(cart.items().get("Keyboard").unwrap(), &1);
-    assert_eq!(cart.items().get("Monitor").unwrap(), &1);
-    assert_eq!(cart.items().get("Headphones").unwrap(), &1);
+    assert_eq!(
+        cart.items().get(&("Laptop".to_string(), None)).unwrap().quantity,
+        2
+    );
+    assert_eq!(
+        cart.items().get(&("Mouse".to_string(), None)).unwrap().quantity,
+        1
+    );
+    assert_eq!(
+        cart.items().get(&("Keyboard".to_string(), None)).unwrap().quantity,
+        1
+    );
+    assert_eq!(
+        cart.items().get(&("Monitor".to_string(), None)).unwrap().quantity,
+        1
+    );
+    assert_eq!(
+        cart.items()
+            .get(&("Headphones".to_string(), None))
+            .unwrap()
+            .quantity,
+        1
+    );
+}
+
+#[test]
+fn test_add_item_unconfigured_variant_rejected() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+
+    // The default catalog has no configured variants, so only `None` is sellable.
+    let result = cart.add_item("Laptop", Some("Red / Large"), 1, QuantityUnit::Piece);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), "Item not found in the catalog: 'Laptop'");
+}
+
+#[test]
+fn test_add_item_distinct_variants_tracked_independently() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.catalog_mut()
+        .set_variants(
+            "Laptop",
+            vec!["Red / Large".to_string(), "Blue / Small".to_string()],
+        )
+        .unwrap();
+
+    cart.add_item("Laptop", Some("Red / Large"), 1, QuantityUnit::Piece)
+        .unwrap();
+    cart.add_item("Laptop", Some("Blue / Small"), 2, QuantityUnit::Piece)
+        .unwrap();
+    assert_eq!(cart.items().len(), 2);
+    assert_eq!(
+        cart.items()
+            .get(&("Laptop".to_string(), Some("Red / Large".to_string())))
+            .unwrap()
+            .quantity,
+        1
+    );
+    assert_eq!(
+        cart.items()
+            .get(&("Laptop".to_string(), Some("Blue / Small".to_string())))
+            .unwrap()
+            .quantity,
+        2
+    );
+
+    cart.update_item("Laptop", Some("Red / Large"), 5).unwrap();
+    assert_eq!(
+        cart.items()
+            .get(&("Laptop".to_string(), Some("Red / Large".to_string())))
+            .unwrap()
+            .quantity,
+        5
+    );
+    assert_eq!(
+        cart.items()
+            .get(&("Laptop".to_string(), Some("Blue / Small".to_string())))
+            .unwrap()
+            .quantity,
+        2
+    );
+
+    cart.remove_item("Laptop", Some("Red / Large")).unwrap();
+    assert_eq!(cart.items().len(), 1);
+    assert!(cart
+        .items()
+        .get(&("Laptop".to_string(), Some("Blue / Small".to_string())))
+        .is_some());
+}
+
+#[test]
+fn test_add_item_mismatched_unit_rejected() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.catalog_mut()
+        .set_units("Laptop", vec![QuantityUnit::Piece, QuantityUnit::Kilogram])
+        .unwrap();
+
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    let result = cart.add_item("Laptop", None, 1, QuantityUnit::Kilogram);
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err(),
+        "Item 'Laptop' is already in the cart measured in Piece, cannot add it in Kilogram"
+    );
 }
 
 #[test]
@@ -82,7 +186,7 @@ fn test_add_item_not_found() {
     let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
     assert_eq!(cart.items().len(), 0);
 
-    let result = cart.add_item("Tablet", 1);
+    let result = cart.add_item("Tablet", None, 1, QuantityUnit::Piece);
     assert!(result.is_err());
     assert_eq!(
         result.unwrap_err(),
@@ -93,23 +197,32 @@ fn test_add_item_not_found() {
 #[test]
 fn test_add_item_zero_quantity() {
     let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
-    let result = cart.add_item("Laptop", 0);
+    let result = cart.add_item("Laptop", None, 0, QuantityUnit::Piece);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Quantity must be nonzero");
+    assert_eq!(
+        result.unwrap_err(),
+        "Quantity for item 'Laptop' must be between 1 and 100"
+    );
 }
 
 #[test]
 fn test_add_item_exceeds_limit() {
     let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
 
-    cart.add_item("Laptop", 50).unwrap();
-    let result = cart.add_item("Laptop", 51);
+    cart.add_item("Laptop", None, 50, QuantityUnit::Piece).unwrap();
+    let result = cart.add_item("Laptop", None, 51, QuantityUnit::Piece);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Quantity exceeds the limit of 100");
+    assert_eq!(
+        result.unwrap_err(),
+        "Adding 51 of 'Laptop' exceeds the limit of 100"
+    );
 
-    let result = cart.add_item("Laptop", 101);
+    let result = cart.add_item("Laptop", None, 101, QuantityUnit::Piece);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Quantity exceeds the limit of 100");
+    assert_eq!(
+        result.unwrap_err(),
+        "Quantity for item 'Laptop' must be between 1 and 100"
+    );
 }
 
 #[test]
@@ -117,29 +230,41 @@ fn test_update_item() {
     let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
     assert_eq!(cart.items().len(), 0);
 
-    cart.add_item("Laptop", 1).unwrap();
-    cart.add_item("Mouse", 1).unwrap();
-    cart.add_item("Keyboard", 1).unwrap();
-    cart.add_item("Monitor", 1).unwrap();
-    cart.add_item("Headphones", 1).unwrap();
-
-    assert_eq!(cart.items().get("Laptop").unwrap(), &1);
-    assert_eq!(cart.items().get("Mouse").unwrap(), &1);
-    assert_eq!(cart.items().get("Keyboard").unwrap(), &1);
-    assert_eq!(cart.items().get("Monitor").unwrap(), &1);
-    assert_eq!(cart.items().get("Headphones").unwrap(), &1);
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Mouse", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Keyboard", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Monitor", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Headphones", None, 1, QuantityUnit::Piece).unwrap();
 
-    cart.update_item("Laptop", 2).unwrap();
-    cart.update_item("Mouse", 2).unwrap();
-    cart.update_item("Keyboard", 2).unwrap();
-    cart.update_item("Monitor", 2).unwrap();
-    cart.update_item("Headphones", 2).unwrap();
+    cart.update_item("Laptop", None, 2).unwrap();
+    cart.update_item("Mouse", None, 2).unwrap();
+    cart.update_item("Keyboard", None, 2).unwrap();
+    cart.update_item("Monitor", None, 2).unwrap();
+    cart.update_item("Headphones", None, 2).unwrap();
 
-    assert_eq!(cart.items().get("Laptop").unwrap(), &2);
-    assert_eq!(cart.items().get("Mouse").unwrap(), &2);
-    assert_eq!(cart.items().get("Keyboard").unwrap(), &2);
-    assert_eq!(cart.items().get("Monitor").unwrap(), &2);
-    assert_eq!(cart.items().get("Headphones").unwrap(), &2);
+    assert_eq!(
+        cart.items().get(&("Laptop".to_string(), None)).unwrap().quantity,
+        2
+    );
+    assert_eq!(
+        cart.items().get(&("Mouse".to_string(), None)).unwrap().quantity,
+        2
+    );
+    assert_eq!(
+        cart.items().get(&("Keyboard".to_string(), None)).unwrap().quantity,
+        2
+    );
+    assert_eq!(
+        cart.items().get(&("Monitor".to_string(), None)).unwrap().quantity,
+        2
+    );
+    assert_eq!(
+        cart.items()
+            .get(&("Headphones".to_string(), None))
+            .unwrap()
+            .quantity,
+        2
+    );
 }
 
 #[test]
@@ -147,7 +272,7 @@ fn test_update_item_not_found() {
     let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
     assert_eq!(cart.items().len(), 0);
 
-    let result = cart.update_item("Tablet", 1);
+    let result = cart.update_item("Tablet", None, 1);
     assert!(result.is_err());
     assert_eq!(result.unwrap_err(), "Item not found in the cart: 'Tablet'");
 }
@@ -155,8 +280,8 @@ fn test_update_item_not_found() {
 #[test]
 fn test_update_item_zero_quantity() {
     let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
-    cart.add_item("Laptop", 1).unwrap();
-    let result = cart.update_item("Laptop", 0);
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    let result = cart.update_item("Laptop", None, 0);
     assert!(result.is_err());
     assert_eq!(result.unwrap_err(), "Quantity must be between 1 and 100");
 }
@@ -164,8 +289,8 @@ fn test_update_item_zero_quantity() {
 #[test]
 fn test_update_item_exceeds_limit() {
     let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
-    cart.add_item("Laptop", 50).unwrap();
-    let result = cart.update_item("Laptop", 101);
+    cart.add_item("Laptop", None, 50, QuantityUnit::Piece).unwrap();
+    let result = cart.update_item("Laptop", None, 101);
     assert!(result.is_err());
     assert_eq!(result.unwrap_err(), "Quantity must be between 1 and 100");
 }
@@ -175,27 +300,27 @@ fn test_remove_item() {
     let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
     assert_eq!(cart.items().len(), 0);
 
-    cart.add_item("Laptop", 1).unwrap();
-    cart.add_item("Mouse", 1).unwrap();
-    cart.add_item("Keyboard", 1).unwrap();
-    cart.add_item("Monitor", 1).unwrap();
-    cart.add_item("Headphones", 1).unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Mouse", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Keyboard", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Monitor", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Headphones", None, 1, QuantityUnit::Piece).unwrap();
 
     assert_eq!(cart.items().len(), 5);
 
-    cart.remove_item("Laptop").unwrap();
+    cart.remove_item("Laptop", None).unwrap();
     assert_eq!(cart.items().len(), 4);
 
-    cart.remove_item("Mouse").unwrap();
+    cart.remove_item("Mouse", None).unwrap();
     assert_eq!(cart.items().len(), 3);
 
-    cart.remove_item("Keyboard").unwrap();
+    cart.remove_item("Keyboard", None).unwrap();
     assert_eq!(cart.items().len(), 2);
 
-    cart.remove_item("Monitor").unwrap();
+    cart.remove_item("Monitor", None).unwrap();
     assert_eq!(cart.items().len(), 1);
 
-    cart.remove_item("Headphones").unwrap();
+    cart.remove_item("Headphones", None).unwrap();
     assert_eq!(cart.items().len(), 0);
 }
 
@@ -204,7 +329,7 @@ fn test_remove_item_not_found() {
     let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
     assert_eq!(cart.items().len(), 0);
 
-    let result = cart.remove_item("Tablet");
+    let result = cart.remove_item("Tablet", None);
     assert!(result.is_err());
     assert_eq!(result.unwrap_err(), "Item not found in the cart: 'Tablet'");
 }
@@ -214,26 +339,252 @@ fn test_get_total_cost() {
     let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
     assert_eq!(cart.get_total_cost(), 0.0);
 
-    cart.add_item("Laptop", 1).unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
     assert_eq!(cart.get_total_cost(), 999.99);
 
-    cart.add_item("Mouse", 1).unwrap();
+    cart.add_item("Mouse", None, 1, QuantityUnit::Piece).unwrap();
     assert_eq!(cart.get_total_cost(), 1025.98);
 
-    cart.add_item("Keyboard", 1).unwrap();
+    cart.add_item("Keyboard", None, 1, QuantityUnit::Piece).unwrap();
     assert_eq!(cart.get_total_cost(), 1075.97);
 
-    cart.add_item("Monitor", 1).unwrap();
+    cart.add_item("Monitor", None, 1, QuantityUnit::Piece).unwrap();
     assert_eq!(cart.get_total_cost(), 1275.96);
 
-    cart.add_item("Headphones", 1).unwrap();
+    cart.add_item("Headphones", None, 1, QuantityUnit::Piece).unwrap();
     assert_eq!(cart.get_total_cost(), 1365.95);
 
-    cart.add_item("Laptop", 1).unwrap();
-    cart.add_item("Mouse", 1).unwrap();
-    cart.add_item("Keyboard", 1).unwrap();
-    cart.add_item("Monitor", 1).unwrap();
-    cart.add_item("Headphones", 1).unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Mouse", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Keyboard", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Monitor", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Headphones", None, 1, QuantityUnit::Piece).unwrap();
 
     assert_eq!(cart.get_total_cost(), 2731.90);
 }
+
+#[test]
+fn test_percent_off_cart_wide() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    cart.apply_promotion(Promotion::PercentOff {
+        item: None,
+        percent: 10.0,
+    });
+
+    assert_eq!(cart.get_total_cost(), 999.99 * 0.9);
+}
+
+#[test]
+fn test_percent_off_single_item() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Mouse", None, 1, QuantityUnit::Piece).unwrap();
+    cart.apply_promotion(Promotion::PercentOff {
+        item: Some("Mouse".to_string()),
+        percent: 50.0,
+    });
+
+    assert_eq!(cart.get_total_cost(), 999.99 + 25.99 * 0.5);
+}
+
+#[test]
+fn test_buy_n_get_m_free() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.add_item("Mouse", None, 2, QuantityUnit::Piece).unwrap();
+    cart.apply_promotion(Promotion::BuyNGetMFree {
+        item: "Mouse".to_string(),
+        buy: 1,
+        free: 1,
+    });
+
+    // 2 mice / (1 + 1) * 1 free = 1 free mouse
+    assert_eq!(cart.get_total_cost(), 25.99);
+}
+
+#[test]
+fn test_buy_n_get_m_free_overflow_does_not_panic() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.add_item("Mouse", None, 2, QuantityUnit::Piece).unwrap();
+    cart.apply_promotion(Promotion::BuyNGetMFree {
+        item: "Mouse".to_string(),
+        buy: u32::MAX,
+        free: 1,
+    });
+
+    // buy + free would overflow u32; the promotion should degrade to no discount
+    // instead of panicking.
+    assert_eq!(cart.get_total_cost(), 25.99 * 2.0);
+}
+
+#[test]
+fn test_spend_threshold() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    cart.apply_promotion(Promotion::SpendThreshold {
+        min_total: 500.0,
+        flat_off: 100.0,
+    });
+
+    assert_eq!(cart.get_total_cost(), 999.99 - 100.0);
+}
+
+#[test]
+fn test_spend_threshold_not_met() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.add_item("Mouse", None, 1, QuantityUnit::Piece).unwrap();
+    cart.apply_promotion(Promotion::SpendThreshold {
+        min_total: 500.0,
+        flat_off: 100.0,
+    });
+
+    assert_eq!(cart.get_total_cost(), 25.99);
+}
+
+#[test]
+fn test_total_clamped_at_zero() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.add_item("Mouse", None, 1, QuantityUnit::Piece).unwrap();
+    cart.apply_promotion(Promotion::SpendThreshold {
+        min_total: 0.0,
+        flat_off: 1000.0,
+    });
+
+    assert_eq!(cart.get_total_cost(), 0.0);
+}
+
+#[test]
+fn test_get_discount_breakdown() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    cart.apply_promotion(Promotion::PercentOff {
+        item: None,
+        percent: 10.0,
+    });
+    cart.apply_promotion(Promotion::SpendThreshold {
+        min_total: 500.0,
+        flat_off: 50.0,
+    });
+
+    let breakdown = cart.get_discount_breakdown();
+    assert_eq!(breakdown.len(), 2);
+    assert_eq!(breakdown[0].1, 999.99 * 0.1);
+    assert_eq!(breakdown[1].1, 50.0);
+}
+
+#[test]
+fn test_checkout() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    let cart_id = cart.id();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Mouse", None, 2, QuantityUnit::Piece).unwrap();
+
+    let order = cart.checkout(Some("Leave at the front desk".to_string())).unwrap();
+    assert_eq!(order.cart_id(), cart_id);
+    assert_eq!(order.customer_id(), "abc12345de-A");
+    assert_eq!(order.note(), Some("Leave at the front desk"));
+    assert_eq!(order.items().len(), 2);
+    assert_eq!(order.total(), 999.99 + 25.99 * 2.0);
+}
+
+#[test]
+fn test_checkout_applies_promotions_to_total() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    cart.apply_promotion(Promotion::PercentOff {
+        item: None,
+        percent: 10.0,
+    });
+
+    let expected_total = cart.get_total_cost();
+    let order = cart.checkout(None).unwrap();
+    assert_eq!(order.total(), expected_total);
+    assert_eq!(order.total(), 999.99 * 0.9);
+}
+
+#[test]
+fn test_checkout_empty_cart() {
+    let cart = ShoppingCart::new("abc12345de-A").unwrap();
+    let result = cart.checkout(None);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), "Cannot check out an empty cart");
+}
+
+#[test]
+fn test_checkout_snapshots_price() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+
+    let order = cart.checkout(None).unwrap();
+    let line = order.items().iter().find(|item| item.name == "Laptop").unwrap();
+    assert_eq!(line.unit_price, 999.99);
+    assert_eq!(line.quantity, 1);
+}
+
+#[test]
+fn test_cart_json_round_trip() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    cart.apply_promotion(Promotion::PercentOff {
+        item: None,
+        percent: 10.0,
+    });
+
+    let json = cart.to_json().unwrap();
+    let restored = ShoppingCart::from_json(&json).unwrap();
+
+    assert_eq!(restored.id(), cart.id());
+    assert_eq!(restored.customer_id(), cart.customer_id());
+    assert_eq!(restored.get_total_cost(), cart.get_total_cost());
+}
+
+#[test]
+fn test_cart_from_json_malformed() {
+    let result = ShoppingCart::from_json("not json");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cart_repository_save_and_get() {
+    let mut repo = CartRepository::new();
+    let cart = ShoppingCart::new("abc12345de-A").unwrap();
+    let cart_id = cart.id();
+    repo.save(cart);
+
+    assert!(repo.get(&cart_id).is_some());
+    assert_eq!(repo.get(&cart_id).unwrap().customer_id(), "abc12345de-A");
+}
+
+#[test]
+fn test_cart_repository_get_by_customer() {
+    let mut repo = CartRepository::new();
+    repo.save(ShoppingCart::new("abc12345de-A").unwrap());
+    repo.save(ShoppingCart::new("abc12345de-A").unwrap());
+    repo.save(ShoppingCart::new("xyz98765fg-Q").unwrap());
+
+    assert_eq!(repo.get_by_customer("abc12345de-A").len(), 2);
+    assert_eq!(repo.get_by_customer("xyz98765fg-Q").len(), 1);
+    assert_eq!(repo.get_by_customer("nonexistent-A").len(), 0);
+}
+
+#[test]
+fn test_cart_repository_remove() {
+    let mut repo = CartRepository::new();
+    let cart = ShoppingCart::new("abc12345de-A").unwrap();
+    let cart_id = cart.id();
+    repo.save(cart);
+
+    repo.remove(&cart_id);
+    assert!(repo.get(&cart_id).is_none());
+}
+
+#[test]
+fn test_get_cost_by_category() {
+    let mut cart = ShoppingCart::new("abc12345de-A").unwrap();
+    cart.add_item("Laptop", None, 1, QuantityUnit::Piece).unwrap();
+    cart.add_item("Mouse", None, 1, QuantityUnit::Piece).unwrap();
+
+    let by_category = cart.get_cost_by_category();
+    assert_eq!(by_category.len(), 1);
+    assert_eq!(by_category.get("Uncategorized").unwrap(), &1025.98);
+}